@@ -44,8 +44,13 @@
 //! ```
 
 extern crate nix;
+#[cfg(feature = "hal")]
+extern crate embedded_hal;
+#[cfg(feature = "mio_evented")]
+extern crate mio;
 
-use nix::sys::epoll::*;
+use nix::sys::epoll::{epoll_create1, epoll_ctl, epoll_wait, EpollCreateFlags, EpollEvent, EpollFlags, EpollOp};
+use nix::time::{clock_gettime, ClockId};
 use nix::unistd::close;
 
 use std::io::prelude::*;
@@ -54,12 +59,128 @@ use std::io;
 use std::io::{Error, ErrorKind, SeekFrom};
 use std::fs;
 use std::fs::{File};
+use std::collections::HashMap;
+use std::path::Path;
+use std::thread;
+use std::time::Duration;
+
+/// Number of attempts made on a newly-exported GPIO's attribute file (by
+/// `export()` and by attribute writes) before giving up
+const EXPORT_RETRY_ATTEMPTS : u32 = 10;
+/// Delay between each of those attempts
+const EXPORT_RETRY_DELAY_MS : u64 = 10;
 
 #[derive(Debug)]
 pub struct Pin {
     pin_num : u64,
 }
 
+/// A GPIO controller (e.g. `/sys/class/gpio/gpiochip0`)
+///
+/// Kernel-assigned global pin numbers shift between boots and kernels,
+/// so rather than hardcoding a number like `Pin::new(527)`, a `Chip` lets
+/// you look up a controller by the label it reports (e.g. `pinctrl-bcm2835`)
+/// and address its lines by chip-relative offset instead.
+#[derive(Clone,Debug)]
+pub struct Chip {
+    chip_num : u64,
+    base : u64,
+    ngpio : u64,
+    label : String,
+}
+
+fn read_chip_attr(chip_num : u64, attr_name : &str) -> io::Result<String> {
+    let path = format!("/sys/class/gpio/gpiochip{}/{}", chip_num, attr_name);
+    let mut f = try!(File::open(&path));
+    let mut s = String::new();
+    try!(f.read_to_string(&mut s));
+    Ok(s)
+}
+
+impl Chip {
+    /// Create a `Chip` for the gpiochip with the given chip number
+    ///
+    /// This reads the `base`, `ngpio`, and `label` attributes from
+    /// `/sys/class/gpio/gpiochipN/` but does not modify anything.
+    pub fn new(chip_num : u64) -> io::Result<Chip> {
+        let base = try!(read_chip_attr(chip_num, "base"));
+        let ngpio = try!(read_chip_attr(chip_num, "ngpio"));
+        let label = try!(read_chip_attr(chip_num, "label"));
+        let base = try!(base.trim().parse::<u64>().map_err(|_| {
+            Error::new(ErrorKind::Other, "Unexpected base file contents")
+        }));
+        let ngpio = try!(ngpio.trim().parse::<u64>().map_err(|_| {
+            Error::new(ErrorKind::Other, "Unexpected ngpio file contents")
+        }));
+        Ok(Chip {
+            chip_num: chip_num,
+            base: base,
+            ngpio: ngpio,
+            label: label.trim().to_string(),
+        })
+    }
+
+    /// Enumerate all of the gpiochips known to the system
+    ///
+    /// This scans `/sys/class/gpio/` for entries named `gpiochipN` and
+    /// returns a `Chip` for each one found.
+    pub fn all() -> io::Result<Vec<Chip>> {
+        let mut chips = Vec::new();
+        for entry in try!(fs::read_dir("/sys/class/gpio")) {
+            let entry = try!(entry);
+            let file_name = entry.file_name();
+            let name = match file_name.to_str() {
+                Some(name) => name,
+                None => continue,
+            };
+            if name.starts_with("gpiochip") {
+                if let Ok(chip_num) = name["gpiochip".len()..].parse::<u64>() {
+                    chips.push(try!(Chip::new(chip_num)));
+                }
+            }
+        }
+        Ok(chips)
+    }
+
+    /// Find the gpiochip whose `label` attribute matches `label` exactly
+    pub fn find_by_label(label : &str) -> io::Result<Chip> {
+        for chip in try!(Chip::all()) {
+            if chip.label() == label {
+                return Ok(chip);
+            }
+        }
+        Err(Error::new(ErrorKind::NotFound,
+                        format!("No gpiochip found with label {}", label)))
+    }
+
+    /// The chip number (the `N` in `gpiochipN`)
+    pub fn chip_num(&self) -> u64 {
+        self.chip_num
+    }
+
+    /// The first global GPIO number handled by this chip
+    pub fn base(&self) -> u64 {
+        self.base
+    }
+
+    /// The number of GPIO lines this chip exposes
+    pub fn ngpio(&self) -> u64 {
+        self.ngpio
+    }
+
+    /// The label the kernel reports for this chip (e.g. `pinctrl-bcm2835`)
+    pub fn label(&self) -> &str {
+        &self.label
+    }
+
+    /// Get the `Pin` for the given chip-relative offset
+    ///
+    /// This maps `offset` onto the global sysfs pin number via `base + offset`.
+    pub fn pin(&self, offset : u64) -> Pin {
+        Pin::new(self.base + offset)
+    }
+}
+
 #[derive(Clone,Debug)]
 pub enum Direction {In, Out, High, Low}
 
@@ -75,7 +196,10 @@ macro_rules! try_unexport {
 }
 
 fn from_nix_error(err: ::nix::Error) -> io::Error {
-    io::Error::from_raw_os_error(err.errno() as i32)
+    match err.as_errno() {
+        Some(errno) => io::Error::from_raw_os_error(errno as i32),
+        None => io::Error::new(ErrorKind::Other, "Unknown nix error"),
+    }
 }
 
 /// Flush up to max bytes from the provided files input buffer
@@ -101,16 +225,33 @@ fn get_value_from_file(dev_file: &mut File) -> io::Result<u8> {
 
 impl Pin {
     /// Write all of the provided contents to the specified devFile
+    ///
+    /// The kernel creates a newly-exported GPIO's attribute files
+    /// asynchronously after `export()` returns, briefly owned root-only,
+    /// so a write made immediately after `export()` can otherwise race the
+    /// kernel and fail with `EACCES`.  Retry opening the file for a
+    /// bounded time before giving up.
     fn write_to_device_file(&self, dev_file_name: &str, value: &str) -> io::Result<()> {
         let gpio_path = format!("/sys/class/gpio/gpio{}/{}", self.pin_num, dev_file_name);
-        let mut dev_file = try!(File::create(&gpio_path));
+        let mut last_err = Error::new(ErrorKind::Other, "gpio attribute never became writable");
+        let mut dev_file = None;
+        for attempt in 0..EXPORT_RETRY_ATTEMPTS {
+            match File::create(&gpio_path) {
+                Ok(f) => { dev_file = Some(f); break },
+                Err(err) => last_err = err,
+            }
+            if attempt + 1 < EXPORT_RETRY_ATTEMPTS {
+                thread::sleep(Duration::from_millis(EXPORT_RETRY_DELAY_MS));
+            }
+        }
+        let mut dev_file = try!(dev_file.ok_or(last_err));
         try!(dev_file.write_all(value.as_bytes()));
         Ok(())
     }
 
     fn read_from_device_file(&self, dev_file_name: &str) -> io::Result<String> {
         let gpio_path = format!("/sys/class/gpio/gpio{}/{}", self.pin_num, dev_file_name);
-        let mut dev_file = try!(File::create(&gpio_path));
+        let mut dev_file = try!(File::open(&gpio_path));
         let mut s = String::new();
         try!(dev_file.read_to_string(&mut s));
         Ok(s)
@@ -125,6 +266,28 @@ impl Pin {
         }
     }
 
+    /// Create a new Pin from a sysfs path
+    ///
+    /// The provided `path` may either be a `gpioN` directory directly under
+    /// `/sys/class/gpio` or a path that symlinks into one (as exposed by,
+    /// e.g., a device's `gpio` subdirectory downstream in
+    /// linux-embedded-hal).  The path is canonicalized and its numeric pin
+    /// number resolved from the first `gpioN` component found.
+    pub fn from_path<P: AsRef<Path>>(path: P) -> io::Result<Pin> {
+        let canon = try!(fs::canonicalize(path.as_ref()));
+        for ancestor in canon.ancestors() {
+            if let Some(name) = ancestor.file_name().and_then(|n| n.to_str()) {
+                if name.len() > 4 && name.starts_with("gpio") {
+                    if let Ok(pin_num) = name[4..].parse::<u64>() {
+                        return Ok(Pin::new(pin_num));
+                    }
+                }
+            }
+        }
+        Err(Error::new(ErrorKind::NotFound,
+                        format!("{} does not refer to an exported GPIO", canon.display())))
+    }
+
     /// Run a closure with the GPIO exported
     ///
     /// Prior to the provided closure being executed, the GPIO
@@ -180,9 +343,17 @@ impl Pin {
     /// }
     /// ```
     pub fn export(&self) -> io::Result<()> {
-        if let Err(_) = fs::metadata(&format!("/sys/class/gpio/gpio{}", self.pin_num)) {
+        if !self.is_exported() {
             let mut export_file = try!(File::create("/sys/class/gpio/export"));
             try!(export_file.write_all(format!("{}", self.pin_num).as_bytes()));
+            // The kernel creates gpioN/value asynchronously after the write
+            // above returns, so without waiting here, a get_value() or
+            // set_value() made immediately after export() can otherwise
+            // race the kernel and see ENOENT.  write_to_device_file()
+            // separately retries past the narrower window where the file
+            // exists but is still transiently root-only, which only
+            // matters to callers that go on to write.
+            try!(self.wait_until_attribute_exists("value"));
         }
         Ok(())
     }
@@ -194,13 +365,36 @@ impl Pin {
     /// exported, it will return without error.  That is, whenever
     /// this function returns Ok, the GPIO is not exported.
     pub fn unexport(&self) -> io::Result<()> {
-        if let Ok(_) = fs::metadata(&format!("/sys/class/gpio/gpio{}", self.pin_num)) {
+        if self.is_exported() {
             let mut unexport_file = try!(File::create("/sys/class/gpio/unexport"));
             try!(unexport_file.write_all(format!("{}", self.pin_num).as_bytes()));
         }
         Ok(())
     }
 
+    /// Get whether this Pin is currently exported
+    pub fn is_exported(&self) -> bool {
+        fs::metadata(&format!("/sys/class/gpio/gpio{}", self.pin_num)).is_ok()
+    }
+
+    /// Wait for an attribute file to exist, retrying for a bounded time
+    ///
+    /// Used by `export()` to ride out the window between the kernel
+    /// accepting a pin number on `/sys/class/gpio/export` and it finishing
+    /// creating that GPIO's attribute files.
+    fn wait_until_attribute_exists(&self, dev_file_name: &str) -> io::Result<()> {
+        let path = format!("/sys/class/gpio/gpio{}/{}", self.pin_num, dev_file_name);
+        for attempt in 0..EXPORT_RETRY_ATTEMPTS {
+            if fs::metadata(&path).is_ok() {
+                return Ok(());
+            }
+            if attempt + 1 < EXPORT_RETRY_ATTEMPTS {
+                thread::sleep(Duration::from_millis(EXPORT_RETRY_DELAY_MS));
+            }
+        }
+        Err(Error::new(ErrorKind::NotFound, format!("{} never appeared after export", path)))
+    }
+
     /// Get the pin number for the Pin
     pub fn get_pin(&self) -> u64 {
         self.pin_num
@@ -319,12 +513,65 @@ impl Pin {
     pub fn get_poller(&self) -> io::Result<PinPoller> {
         PinPoller::new(self.pin_num)
     }
+
+    /// Read the contents of an arbitrary sysfs attribute of this GPIO
+    ///
+    /// This is the same mechanism `get_direction()`, `get_value()`, etc. are
+    /// built on, exposed directly so that sysfs entries this crate doesn't
+    /// have a dedicated method for (e.g. `active_low`) don't each need a
+    /// bespoke accessor.
+    pub fn attribute(&self, name: &str) -> io::Result<String> {
+        self.read_from_device_file(name)
+    }
+
+    /// Write to an arbitrary sysfs attribute of this GPIO
+    ///
+    /// See `attribute()`.
+    pub fn set_attribute(&self, name: &str, value: &str) -> io::Result<()> {
+        self.write_to_device_file(name, value)
+    }
+
+    /// Get whether the "active low" setting is enabled for this Pin
+    ///
+    /// When this is true, the `value` file's logical sense is inverted
+    /// relative to the signal level on the physical pin.
+    pub fn get_active_low(&self) -> io::Result<bool> {
+        match try!(self.attribute("active_low")).trim() {
+            "1" => Ok(true),
+            "0" => Ok(false),
+            other => Err(Error::new(ErrorKind::Other,
+                                    format!("Unexpected active_low file contents {}", other))),
+        }
+    }
+
+    /// Set the "active low" setting for this Pin
+    ///
+    /// When set to true, the logical sense of the `value` file is
+    /// inverted relative to the signal level on the physical pin.
+    pub fn set_active_low(&self, active_low: bool) -> io::Result<()> {
+        self.set_attribute("active_low", if active_low { "1" } else { "0" })
+    }
 }
 
 pub struct PinPoller {
     pin_num : u64,
     epoll_fd : RawFd,
     devfile : File,
+    last_value : Option<u8>,
+}
+
+/// A single timestamped, direction-tagged edge event
+///
+/// Unlike the bare `Option<u8>` returned by `PinPoller::poll`, a `GpioEvent`
+/// records both the edge that fired (`RisingEdge`/`FallingEdge`, inferred by
+/// comparing against the previously observed value) and the monotonic time
+/// at which it was observed, so callers can order and distinguish
+/// transitions even under `Edge::BothEdges`.
+#[derive(Clone,Debug)]
+pub struct GpioEvent {
+    pub timestamp : Duration,
+    pub edge : Edge,
+    pub value : u8,
 }
 
 impl PinPoller {
@@ -341,19 +588,16 @@ impl PinPoller {
     pub fn new(pin_num : u64) -> io::Result<PinPoller> {
         let devfile : File = try!(File::open(&format!("/sys/class/gpio/gpio{}/value", pin_num)));
         let devfile_fd = devfile.as_raw_fd();
-        let epoll_fd = try!(epoll_create().map_err(from_nix_error));
-        let events = EPOLLPRI | EPOLLET;
-        let info = EpollEvent {
-            events: events,
-            data: 0u64,
-        };
+        let epoll_fd = try!(epoll_create1(EpollCreateFlags::empty()).map_err(from_nix_error));
+        let mut info = EpollEvent::new(EpollFlags::EPOLLPRI | EpollFlags::EPOLLET, 0u64);
 
-        match epoll_ctl(epoll_fd, EpollOp::EpollCtlAdd, devfile_fd, &info) {
+        match epoll_ctl(epoll_fd, EpollOp::EpollCtlAdd, devfile_fd, Some(&mut info)) {
             Ok(_) => {
                 Ok(PinPoller {
                     pin_num: pin_num,
                     devfile: devfile,
                     epoll_fd: epoll_fd,
+                    last_value: None,
                 })
             },
             Err(err) => {
@@ -382,14 +626,47 @@ impl PinPoller {
     /// occurred and the current time.
     pub fn poll(&mut self, timeout_ms: isize) -> io::Result<Option<u8>> {
         try!(flush_input_from_file(&mut self.devfile, 255));
-        let dummy_event = EpollEvent { events: EPOLLPRI | EPOLLET, data: 0u64};
-        let mut events: [EpollEvent; 1] = [ dummy_event ];
+        let mut events: [EpollEvent; 1] = [ EpollEvent::empty() ];
         let cnt = try!(epoll_wait(self.epoll_fd, &mut events, timeout_ms).map_err(from_nix_error));
         Ok(match cnt {
             0 => None, // timeout
             _ => Some(try!(get_value_from_file(&mut self.devfile))),
         })
     }
+
+    /// Block until an interrupt occurs, returning a timestamped edge event
+    ///
+    /// This behaves like `poll()`, but captures a `CLOCK_MONOTONIC`
+    /// timestamp immediately after `epoll_wait` returns and infers whether
+    /// a `RisingEdge` or `FallingEdge` occurred by comparing the newly read
+    /// value against the value observed on the previous call.  Returns
+    /// `None` if `timeout_ms` elapses with no interrupt.
+    pub fn poll_event(&mut self, timeout_ms: isize) -> io::Result<Option<GpioEvent>> {
+        try!(flush_input_from_file(&mut self.devfile, 255));
+        let mut events: [EpollEvent; 1] = [ EpollEvent::empty() ];
+        let cnt = try!(epoll_wait(self.epoll_fd, &mut events, timeout_ms).map_err(from_nix_error));
+        if cnt == 0 {
+            return Ok(None);
+        }
+        let ts = try!(clock_gettime(ClockId::CLOCK_MONOTONIC).map_err(from_nix_error));
+        let value = try!(get_value_from_file(&mut self.devfile));
+        let edge = match self.last_value {
+            Some(prev) if value > prev => Edge::RisingEdge,
+            Some(prev) if value < prev => Edge::FallingEdge,
+            // Either the first event on this poller, or a glitch under
+            // BothEdges where the value read back matches what we already
+            // had on file (e.g. two transitions coalesced before we got to
+            // read it) -- there's no way to tell the true edge apart from
+            // the current level here, so fall back to guessing from it.
+            _ => if value == 1 { Edge::RisingEdge } else { Edge::FallingEdge },
+        };
+        self.last_value = Some(value);
+        Ok(Some(GpioEvent {
+            timestamp: Duration::new(ts.tv_sec() as u64, ts.tv_nsec() as u32),
+            edge: edge,
+            value: value,
+        }))
+    }
 }
 
 impl Drop for PinPoller {
@@ -400,3 +677,205 @@ impl Drop for PinPoller {
         close(self.epoll_fd).unwrap();  // panic! if close files
     }
 }
+
+/// Polls many pins for interrupts using a single epoll instance
+///
+/// Unlike `PinPoller`, which can only wait on a single GPIO, a
+/// `MultiPinPoller` registers the `value` file of every pin it is
+/// given with one shared `epoll_fd` and uses the `EpollEvent.data`
+/// field to carry the pin number so that a wake-up can be attributed
+/// back to the `Pin` that caused it.
+pub struct MultiPinPoller {
+    epoll_fd : RawFd,
+    devfiles : HashMap<u64, File>,
+}
+
+impl MultiPinPoller {
+    /// Create a new MultiPinPoller for the provided pin numbers
+    pub fn new(pin_nums : &[u64]) -> io::Result<MultiPinPoller> {
+        let epoll_fd = try!(epoll_create1(EpollCreateFlags::empty()).map_err(from_nix_error));
+        let mut devfiles = HashMap::new();
+        for &pin_num in pin_nums {
+            let devfile : File = match File::open(&format!("/sys/class/gpio/gpio{}/value", pin_num)) {
+                Ok(f) => f,
+                Err(err) => { let _ = close(epoll_fd); return Err(err) },
+            };
+            let mut info = EpollEvent::new(EpollFlags::EPOLLPRI | EpollFlags::EPOLLET, pin_num);
+            if let Err(err) = epoll_ctl(epoll_fd, EpollOp::EpollCtlAdd, devfile.as_raw_fd(), Some(&mut info)) {
+                let _ = close(epoll_fd);
+                return Err(from_nix_error(err));
+            }
+            devfiles.insert(pin_num, devfile);
+        }
+        Ok(MultiPinPoller {
+            epoll_fd: epoll_fd,
+            devfiles: devfiles,
+        })
+    }
+
+    /// Block until an interrupt occurs on any of the registered pins
+    ///
+    /// Returns a `(pin_num, value)` pair for every pin that fired,
+    /// flushing and re-reading its `value` file before reporting the
+    /// current value.  Returns an empty `Vec` if `timeout_ms` elapses
+    /// with no interrupts.
+    pub fn poll(&mut self, timeout_ms: isize) -> io::Result<Vec<(u64, u8)>> {
+        for devfile in self.devfiles.values_mut() {
+            try!(flush_input_from_file(devfile, 255));
+        }
+        let mut events = vec![EpollEvent::empty(); self.devfiles.len()];
+        let cnt = try!(epoll_wait(self.epoll_fd, &mut events, timeout_ms).map_err(from_nix_error));
+        let mut result = Vec::with_capacity(cnt);
+        for event in &events[..cnt] {
+            let pin_num = event.data();
+            if let Some(devfile) = self.devfiles.get_mut(&pin_num) {
+                result.push((pin_num, try!(get_value_from_file(devfile))));
+            }
+        }
+        Ok(result)
+    }
+}
+
+impl Drop for MultiPinPoller {
+    fn drop(&mut self) {
+        // see PinPoller::drop(); the epoll fd does not implement
+        // Drop itself so we must close it explicitly
+        close(self.epoll_fd).unwrap();
+    }
+}
+
+/// Error type for the optional embedded-hal trait implementations
+///
+/// The rest of this crate returns `std::io::Error` directly since that
+/// is the error type sysfs access naturally produces, but the
+/// embedded-hal traits require an associated `Error` type, so this enum
+/// exists to bridge the two (and any `nix` error that might slip through)
+/// without losing the underlying cause.  Named `GpioError` rather than
+/// `Error` so it doesn't collide with `std::io::Error`, which this module
+/// already imports unconditionally.
+#[cfg(feature = "hal")]
+#[derive(Debug)]
+pub enum GpioError {
+    Io(io::Error),
+    Nix(::nix::Error),
+}
+
+#[cfg(feature = "hal")]
+impl From<io::Error> for GpioError {
+    fn from(err: io::Error) -> GpioError {
+        GpioError::Io(err)
+    }
+}
+
+#[cfg(feature = "hal")]
+impl From<::nix::Error> for GpioError {
+    fn from(err: ::nix::Error) -> GpioError {
+        GpioError::Nix(err)
+    }
+}
+
+#[cfg(feature = "hal")]
+impl ::std::fmt::Display for GpioError {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        match *self {
+            GpioError::Io(ref e) => write!(f, "{}", e),
+            GpioError::Nix(ref e) => write!(f, "{}", e),
+        }
+    }
+}
+
+#[cfg(feature = "hal")]
+impl ::std::error::Error for GpioError {
+    fn description(&self) -> &str {
+        match *self {
+            GpioError::Io(ref e) => e.description(),
+            GpioError::Nix(_) => "nix error",
+        }
+    }
+}
+
+/// Maps `Pin::get_value`/`set_value` onto the embedded-hal digital IO traits
+///
+/// This is gated behind the `hal` feature so that pulling in the
+/// `embedded-hal` dependency stays opt-in, matching how linux-embedded-hal
+/// wraps this crate externally today -- with this, a `sysfs_gpio::Pin` can
+/// be used directly by any sensor/display driver written against
+/// embedded-hal without a separate newtype shim.  These are the fallible
+/// `digital::v2` traits (the infallible `digital::v1` traits don't apply
+/// here since sysfs access can fail at any point).
+#[cfg(feature = "hal")]
+impl ::embedded_hal::digital::v2::InputPin for Pin {
+    type Error = GpioError;
+
+    fn is_high(&self) -> Result<bool, GpioError> {
+        Ok(try!(self.get_value()) != 0)
+    }
+
+    fn is_low(&self) -> Result<bool, GpioError> {
+        Ok(try!(self.get_value()) == 0)
+    }
+}
+
+#[cfg(feature = "hal")]
+impl ::embedded_hal::digital::v2::OutputPin for Pin {
+    type Error = GpioError;
+
+    fn set_high(&mut self) -> Result<(), GpioError> {
+        Ok(try!(self.set_value(1)))
+    }
+
+    fn set_low(&mut self) -> Result<(), GpioError> {
+        Ok(try!(self.set_value(0)))
+    }
+}
+
+#[cfg(feature = "hal")]
+impl ::embedded_hal::digital::v2::ToggleableOutputPin for Pin {
+    type Error = GpioError;
+
+    fn toggle(&mut self) -> Result<(), GpioError> {
+        let cur = try!(self.get_value());
+        Ok(try!(self.set_value(if cur == 0 { 1 } else { 0 })))
+    }
+}
+
+// `mio::unix::SourceFd` lives behind mio's `os-ext` feature, which a
+// `Cargo.toml` enabling `mio_evented` needs to turn on alongside `mio`'s
+// `event::Source`/`Registry`/`Token`/`Interest` re-exports.
+#[cfg(feature = "mio_evented")]
+impl ::mio::event::Source for PinPoller {
+    /// Register this PinPoller's underlying `value` file descriptor with a mio `Registry`
+    ///
+    /// This lets a single Tokio/mio reactor await level/edge interrupts
+    /// from dozens of GPIOs without a dedicated blocking thread per pin,
+    /// picking up where the `Drop` impl's "similar to how mio works"
+    /// comment about our own `epoll_fd` handling left off.
+    fn register(&mut self, registry: &::mio::Registry, token: ::mio::Token, interests: ::mio::Interest) -> io::Result<()> {
+        ::mio::unix::SourceFd(&self.devfile.as_raw_fd()).register(registry, token, interests)
+    }
+
+    fn reregister(&mut self, registry: &::mio::Registry, token: ::mio::Token, interests: ::mio::Interest) -> io::Result<()> {
+        ::mio::unix::SourceFd(&self.devfile.as_raw_fd()).reregister(registry, token, interests)
+    }
+
+    fn deregister(&mut self, registry: &::mio::Registry) -> io::Result<()> {
+        ::mio::unix::SourceFd(&self.devfile.as_raw_fd()).deregister(registry)
+    }
+}
+
+#[cfg(feature = "mio_evented")]
+impl PinPoller {
+    /// Non-blocking counterpart to `poll_event()` for use with an external reactor
+    ///
+    /// Call this after mio/Tokio reports this PinPoller's registered fd as
+    /// readable.  Rather than sleeping in `epoll_wait` the way `poll()`/
+    /// `poll_event()` do, this performs a zero-timeout check and returns
+    /// `io::ErrorKind::WouldBlock` if no interrupt is pending on this
+    /// PinPoller's own epoll instance.
+    pub fn try_read_event(&mut self) -> io::Result<GpioEvent> {
+        match try!(self.poll_event(0)) {
+            Some(event) => Ok(event),
+            None => Err(Error::new(ErrorKind::WouldBlock, "no gpio event ready")),
+        }
+    }
+}